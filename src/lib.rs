@@ -1,7 +1,11 @@
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
 use image::{DynamicImage, GenericImageView, ImageError, RgbImage};
+use rayon::prelude::*;
 
 #[derive(thiserror::Error, Debug)]
 pub enum TilingError {
@@ -19,6 +23,180 @@ pub enum TilingError {
 
 pub type DZIResult<T, E = TilingError> = Result<T, E>;
 
+/// Output image format used to encode individual tiles.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TileFormat {
+    /// Baseline JPEG, `quality` in `0..=100`.
+    Jpeg { quality: u8 },
+    /// Lossless PNG.
+    Png,
+    /// WebP, `quality` in `0.0..=100.0`, ignored when `lossless` is set.
+    WebP { quality: f32, lossless: bool },
+}
+
+impl TileFormat {
+    /// File extension (without leading dot) tiles of this format are saved with.
+    fn extension(&self) -> &'static str {
+        match self {
+            TileFormat::Jpeg { .. } => "jpg",
+            TileFormat::Png => "png",
+            TileFormat::WebP { .. } => "webp",
+        }
+    }
+
+    /// Value written into the `.dzi` descriptor's `Format=` attribute.
+    fn descriptor_name(&self) -> &'static str {
+        self.extension()
+    }
+}
+
+/// Tile pyramid output layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    /// Microsoft Deep Zoom: a `.dzi` descriptor plus `{level}/{col}_{row}.ext` tiles.
+    DeepZoom,
+    /// IIIF Image API static tile tree plus an `info.json` manifest.
+    Iiif,
+}
+
+/// Lays out where tiles are written and how the pyramid's manifest is described.
+/// Implemented once per [`Layout`] variant.
+trait LayoutStrategy {
+    /// Pixel bounds `(x, y, x2, y2)` a tile's cropped content should cover
+    /// at this level. Deep Zoom tiles overlap their neighbors so an
+    /// OpenSeadragon-style viewer can stitch them seamlessly; IIIF tiles
+    /// must align exactly to the declared grid with no overlap, since
+    /// viewers request them by that grid's canonical region/size.
+    fn tile_bounds(&self, tc: &TileCreator, level: u32, col: u32, row: u32) -> DZIResult<(u32, u32, u32, u32)>;
+
+    /// Path a given tile is (or will be) saved at
+    fn tile_path(&self, tc: &TileCreator, level: u32, col: u32, row: u32) -> DZIResult<PathBuf>;
+
+    /// Path of the manifest describing the whole pyramid (`.dzi` or `info.json`)
+    fn manifest_path(&self, tc: &TileCreator) -> PathBuf;
+
+    /// Write the manifest describing the whole pyramid (`.dzi` or `info.json`)
+    fn write_manifest(&self, tc: &TileCreator) -> DZIResult<()>;
+}
+
+struct DeepZoomLayout;
+
+impl LayoutStrategy for DeepZoomLayout {
+    fn tile_bounds(&self, tc: &TileCreator, level: u32, col: u32, row: u32) -> DZIResult<(u32, u32, u32, u32)> {
+        tc.get_tile_bounds(level, col, row)
+    }
+
+    fn tile_path(&self, tc: &TileCreator, level: u32, col: u32, row: u32) -> DZIResult<PathBuf> {
+        Ok(tc
+            .dest_path
+            .join(format!("{}", level))
+            .join(format!("{}_{}.{}", col, row, tc.format.extension())))
+    }
+
+    fn manifest_path(&self, tc: &TileCreator) -> PathBuf {
+        tc.dzi_file_path.clone()
+    }
+
+    fn write_manifest(&self, tc: &TileCreator) -> DZIResult<()> {
+        let (w, h) = tc.image.dimensions();
+        let dzi = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<Image xmlns="http://schemas.microsoft.com/deepzoom/2008"
+    TileSize="{}"
+    Overlap="{}"
+    Format="{}">
+    <Size Width="{}" Height="{}"/>
+</Image>"#,
+            tc.tile_size,
+            tc.tile_overlap,
+            tc.format.descriptor_name(),
+            w,
+            h
+        );
+        let mut f = std::fs::File::create(self.manifest_path(tc))?;
+        f.write_all(dzi.as_bytes())?;
+        Ok(())
+    }
+}
+
+struct IiifLayout;
+
+impl LayoutStrategy for IiifLayout {
+    fn tile_bounds(&self, tc: &TileCreator, level: u32, col: u32, row: u32) -> DZIResult<(u32, u32, u32, u32)> {
+        // Canonical IIIF URLs address a non-overlapping tile grid, so
+        // `tile_overlap` is ignored for this layout's own output tiles.
+        tc.get_core_tile_bounds(level, col, row)
+    }
+
+    fn tile_path(&self, tc: &TileCreator, level: u32, col: u32, row: u32) -> DZIResult<PathBuf> {
+        let (x, y, x2, y2) = self.tile_bounds(tc, level, col, row)?;
+        // IIIF regions are always expressed in full-resolution source-image
+        // coordinates, even when requesting a downsampled level, so the
+        // level-local tile bounds need to be scaled back up. The region
+        // already disambiguates the level, so there's no separate level
+        // segment in the path: canonical IIIF URLs are
+        // `{id}/{region}/{size}/{rotation}/{quality}.{fmt}`.
+        let scale = tc.get_scale(level)?;
+        let region = format!(
+            "{},{},{},{}",
+            (x as f64 / scale).round() as u32,
+            (y as f64 / scale).round() as u32,
+            ((x2 - x) as f64 / scale).round() as u32,
+            ((y2 - y) as f64 / scale).round() as u32
+        );
+        let size = format!("{},{}", x2 - x, y2 - y);
+        Ok(tc
+            .dest_path
+            .join(region)
+            .join(size)
+            .join("0")
+            .join(format!("default.{}", tc.format.extension())))
+    }
+
+    fn manifest_path(&self, tc: &TileCreator) -> PathBuf {
+        tc.dest_path.join("info.json")
+    }
+
+    fn write_manifest(&self, tc: &TileCreator) -> DZIResult<()> {
+        let (w, h) = tc.image.dimensions();
+        let mut sizes = Vec::with_capacity(tc.levels as usize);
+        for l in 0..tc.levels {
+            let (lw, lh) = tc.get_dimensions(l)?;
+            sizes.push(format!(r#"{{"width":{},"height":{}}}"#, lw, lh));
+        }
+        // scaleFactors are listed ascending, full-res level first (factor 1),
+        // each coarser level down halving resolution again.
+        let scale_factors: Vec<String> = (0..tc.levels)
+            .map(|l| (1u32 << l).to_string())
+            .collect();
+        let info = format!(
+            r#"{{
+  "width": {},
+  "height": {},
+  "tiles": [{{"width": {}, "scaleFactors": [{}]}}],
+  "sizes": [{}]
+}}"#,
+            w,
+            h,
+            tc.tile_size,
+            scale_factors.join(", "),
+            sizes.join(", ")
+        );
+        let mut f = std::fs::File::create(self.manifest_path(tc))?;
+        f.write_all(info.as_bytes())?;
+        Ok(())
+    }
+}
+
+impl Layout {
+    fn strategy(&self) -> &'static dyn LayoutStrategy {
+        match self {
+            Layout::DeepZoom => &DeepZoomLayout,
+            Layout::Iiif => &IiifLayout,
+        }
+    }
+}
+
 /// A tile creator, this struct and associated functions
 /// implement the DZI tiler
 pub struct TileCreator {
@@ -34,6 +212,31 @@ pub struct TileCreator {
     pub tile_overlap: u32,
     /// total number of levels of tiles
     pub levels: u32,
+    /// rayon thread pool size used to generate tiles in parallel.
+    /// `None` keeps tile generation serial, `Some(0)` uses rayon's
+    /// global pool (all available cores) and `Some(n)` caps the pool
+    /// at `n` threads.
+    pub threads: Option<usize>,
+    /// image format tiles are encoded and saved as
+    pub format: TileFormat,
+    /// resampling filter used when downscaling the source image and
+    /// stitched parent levels to build each level's image
+    pub filter: image::imageops::FilterType,
+    /// pyramid output layout (Deep Zoom or IIIF)
+    pub layout: Layout,
+    /// build coarser levels by mip-averaging tile quadrants up from the
+    /// level directly above them (in memory) instead of resizing the full
+    /// source image for every level; see [`Self::stitch_from_children`].
+    /// Off by default: stitching tile-by-tile instead of resizing the
+    /// whole level at once is not guaranteed byte-identical to the
+    /// historical output at tile-grid seams, so existing callers keep
+    /// their current pyramids unless they opt in. Enable for faster,
+    /// better-quality pyramids on large sources.
+    pub bottom_up: bool,
+    /// skip re-encoding tiles whose source pixels and encoding parameters
+    /// are unchanged since the last `create_tiles` run, per a content-hash
+    /// sidecar manifest kept alongside the tiles
+    pub incremental: bool,
 }
 
 impl TileCreator {
@@ -78,6 +281,12 @@ impl TileCreator {
             tile_overlap,
             dest_path,
             dzi_file_path,
+            threads: None,
+            format: TileFormat::Jpeg { quality: 75 },
+            filter: image::imageops::FilterType::Lanczos3,
+            layout: Layout::DeepZoom,
+            incremental: false,
+            bottom_up: false,
         })
     }
 
@@ -103,33 +312,222 @@ impl TileCreator {
             levels,
             tile_size,
             tile_overlap,
+            threads: None,
+            format: TileFormat::Jpeg { quality: 75 },
+            filter: image::imageops::FilterType::Lanczos3,
+            layout: Layout::DeepZoom,
+            incremental: false,
+            bottom_up: false,
         })
     }
 
+    /// Enable bottom-up pyramid construction: derive each coarser level by
+    /// downsampling the in-memory image of the level directly above it,
+    /// rather than resizing the full source image at every level.
+    pub fn with_bottom_up(mut self, bottom_up: bool) -> Self {
+        self.bottom_up = bottom_up;
+        self
+    }
+
+    /// Enable incremental mode: tiles whose content hash matches the
+    /// sidecar cache from a previous run are not re-encoded.
+    pub fn with_incremental(mut self, incremental: bool) -> Self {
+        self.incremental = incremental;
+        self
+    }
+
+    /// Set the pyramid output layout.
+    pub fn with_layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Set the format tiles are encoded and saved as.
+    pub fn with_format(mut self, format: TileFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Set the resampling filter used to build each level's image.
+    /// Defaults to `Lanczos3`, which avoids the aliasing `Nearest`
+    /// produces at coarse zoom levels; pass `Nearest` back in for
+    /// pixel-art sources where crisp, unblended edges matter more than
+    /// antialiasing.
+    pub fn with_filter(mut self, filter: image::imageops::FilterType) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Enable parallel tile generation, optionally capping the rayon
+    /// thread pool used to do so. Pass `Some(0)` to use all available
+    /// cores, or `Some(n)` to cap the pool at `n` threads.
+    pub fn with_threads(mut self, threads: Option<usize>) -> Self {
+        self.threads = threads;
+        self
+    }
+
     fn calculate_levels(src_image_width: u32, src_image_height: u32) -> u32 {
         let levels: u32 = (src_image_height.max(src_image_width) as f64).log2().ceil() as u32 + 1;
         return levels;
     }
 
-    /// Create DZI tiles
+    /// Create tiles
     pub fn create_tiles(&self) -> DZIResult<(PathBuf, PathBuf)> {
-        for l in 0..self.levels {
-            self.create_level(l)?;
+        let params_fingerprint = self.params_fingerprint();
+        let source_fingerprint = self.source_fingerprint();
+        let mut loaded_cache = HashMap::new();
+
+        if self.incremental {
+            if let Some((cached_params, cached_source)) = self.read_cache_header() {
+                if cached_params != params_fingerprint {
+                    // Encoding parameters or the tile grid shape changed: any
+                    // existing tiles may be stale or at the wrong extension
+                    // or coordinates, so start from a clean directory rather
+                    // than leaving orphaned files behind.
+                    std::fs::remove_dir_all(&self.dest_path).ok();
+                } else if cached_source == source_fingerprint {
+                    // Nothing changed anywhere since the last run: every tile
+                    // and the manifest are already correct, so skip the
+                    // per-level resampling entirely.
+                    return Ok((self.layout.strategy().manifest_path(self), self.dest_path.clone()));
+                } else {
+                    loaded_cache = self.load_tile_cache();
+                }
+            }
         }
-        let (w, h) = self.image.dimensions();
-        let dzi = format!(
-            r#"<?xml version="1.0" encoding="UTF-8"?>
-<Image xmlns="http://schemas.microsoft.com/deepzoom/2008"
-    TileSize="{}"
-    Overlap="{}"
-    Format="jpg">
-    <Size Width="{}" Height="{}"/>
-</Image>"#,
-            self.tile_size, self.tile_overlap, w, h
-        );
-        let mut f = std::fs::File::create(self.dzi_file_path.as_path())?;
-        f.write_all(dzi.as_bytes())?;
-        Ok((self.dzi_file_path.clone(), self.dest_path.clone()))
+        let cache = Mutex::new(loaded_cache);
+
+        // When `bottom_up` is set, levels are built top-down: every level
+        // below the highest is downsampled from the in-memory image of the
+        // level directly above it, so that level must be computed first.
+        let run_levels = || -> DZIResult<()> {
+            let mut parent_image: Option<DynamicImage> = None;
+            for l in (0..self.levels).rev() {
+                let li = self.get_level_image(l, parent_image.as_ref())?;
+                self.create_level(l, &li, &cache)?;
+                parent_image = if self.bottom_up { Some(li) } else { None };
+            }
+            Ok(())
+        };
+
+        // A capped pool is built once for the whole run rather than per
+        // level so `threads: Some(n)` doesn't pay pool setup per level;
+        // `par_iter` calls inside `create_level` pick it up automatically
+        // since they run on one of its worker threads.
+        match self.threads {
+            Some(n) if n > 0 => {
+                let pool = rayon::ThreadPoolBuilder::new()
+                    .num_threads(n)
+                    .build()
+                    .map_err(|_| TilingError::UnexpectedError)?;
+                pool.install(run_levels)?;
+            }
+            _ => run_levels()?,
+        }
+        self.layout.strategy().write_manifest(self)?;
+        if self.incremental {
+            self.save_tile_cache(&cache.into_inner().unwrap(), params_fingerprint, source_fingerprint)?;
+        }
+        Ok((self.layout.strategy().manifest_path(self), self.dest_path.clone()))
+    }
+
+    /// Path of the sidecar manifest mapping `(level, col, row)` to the
+    /// content hash its tile was last saved with
+    fn tile_cache_path(&self) -> PathBuf {
+        self.dest_path.join(".tile-cache")
+    }
+
+    /// Hash of everything that determines the tile grid's shape and
+    /// encoding, independent of source pixel content: source dimensions
+    /// (two sources can share `levels` while differing in width/height),
+    /// tiling params, and output params. If this differs from a previous
+    /// run's, existing tiles may be at stale coordinates or extensions and
+    /// are not safe to reuse or diff against.
+    fn params_fingerprint(&self) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.image.dimensions().hash(&mut hasher);
+        self.tile_size.hash(&mut hasher);
+        self.tile_overlap.hash(&mut hasher);
+        self.levels.hash(&mut hasher);
+        self.bottom_up.hash(&mut hasher);
+        format!("{:?}", self.layout).hash(&mut hasher);
+        format!("{:?}", self.format).hash(&mut hasher);
+        format!("{:?}", self.filter).hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Hash of the source image's pixels, used to detect a completely
+    /// unchanged source so a rerun can skip all per-level work
+    fn source_fingerprint(&self) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.image.as_bytes().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Read the `(params_fingerprint, source_fingerprint)` header written by
+    /// a previous `create_tiles` run, if the sidecar manifest has one
+    fn read_cache_header(&self) -> Option<(u64, u64)> {
+        let contents = std::fs::read_to_string(self.tile_cache_path()).ok()?;
+        let mut parts = contents.lines().next()?.split(' ');
+        if parts.next()? != "FP" {
+            return None;
+        }
+        let params = u64::from_str_radix(parts.next()?, 16).ok()?;
+        let source = u64::from_str_radix(parts.next()?, 16).ok()?;
+        Some((params, source))
+    }
+
+    /// Load the per-tile content-hash cache written by a previous
+    /// `create_tiles` run, if any
+    fn load_tile_cache(&self) -> HashMap<(u32, u32, u32), u64> {
+        let Ok(contents) = std::fs::read_to_string(self.tile_cache_path()) else {
+            return HashMap::new();
+        };
+        contents
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.split(' ');
+                let level = parts.next()?.parse().ok()?;
+                let col = parts.next()?.parse().ok()?;
+                let row = parts.next()?.parse().ok()?;
+                let hash = u64::from_str_radix(parts.next()?, 16).ok()?;
+                Some(((level, col, row), hash))
+            })
+            .collect()
+    }
+
+    /// Persist the content-hash cache for the next `create_tiles` run,
+    /// headed by the fingerprints `create_tiles` uses to tell whether that
+    /// run's params or source pixels changed
+    fn save_tile_cache(
+        &self,
+        cache: &HashMap<(u32, u32, u32), u64>,
+        params_fingerprint: u64,
+        source_fingerprint: u64,
+    ) -> DZIResult<()> {
+        let mut contents = format!("FP {:016x} {:016x}\n", params_fingerprint, source_fingerprint);
+        for (&(level, col, row), hash) in cache {
+            contents.push_str(&format!("{} {} {} {:016x}\n", level, col, row, hash));
+        }
+        std::fs::write(self.tile_cache_path(), contents)?;
+        Ok(())
+    }
+
+    /// Content hash of a tile's pixels and encoding parameters, used to
+    /// detect whether it needs re-encoding in incremental mode
+    fn tile_content_hash(&self, tile_image: &DynamicImage) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        tile_image.as_bytes().hash(&mut hasher);
+        std::mem::discriminant(&self.format).hash(&mut hasher);
+        match self.format {
+            TileFormat::Jpeg { quality } => quality.hash(&mut hasher),
+            TileFormat::Png => {}
+            TileFormat::WebP { quality, lossless } => {
+                quality.to_bits().hash(&mut hasher);
+                lossless.hash(&mut hasher);
+            }
+        }
+        hasher.finish()
     }
 
     /// Check if level is valid
@@ -140,30 +538,164 @@ impl TileCreator {
         Ok(())
     }
 
-    /// Create tiles for a level
-    fn create_level(&self, level: u32) -> DZIResult<()> {
-        let p = self.dest_path.join(format!("{}", level));
-        std::fs::create_dir_all(&p)?;
-        let mut li = self.get_level_image(level)?;
+    /// Create tiles for a level from its already-built image `li`. When
+    /// `self.incremental` is set, tiles whose content hash is unchanged
+    /// from `cache` are left on disk as-is instead of being re-encoded,
+    /// and `cache` is updated to reflect what was kept or (re)written.
+    fn create_level(
+        &self,
+        level: u32,
+        li: &DynamicImage,
+        cache: &Mutex<HashMap<(u32, u32, u32), u64>>,
+    ) -> DZIResult<()> {
         let (c, r) = self.get_tile_count(level)?;
-        for col in 0..c {
-            for row in 0..r {
-                let (x, y, x2, y2) = self.get_tile_bounds(level, col, row)?;
-                let tile_image = li.crop(x, y, x2 - x, y2 - y);
-                let tile_path = p.join(format!("{}_{}.jpg", col, row));
-                tile_image.save(tile_path)?;
+        let grid: Vec<(u32, u32)> = (0..c).flat_map(|col| (0..r).map(move |row| (col, row))).collect();
+
+        let save_tile = |&(col, row): &(u32, u32)| -> DZIResult<()> {
+            let (x, y, x2, y2) = self.layout.strategy().tile_bounds(self, level, col, row)?;
+            let tile_image = li.crop_imm(x, y, x2 - x, y2 - y);
+            let tile_path = self.tile_path(level, col, row)?;
+
+            if self.incremental {
+                let hash = self.tile_content_hash(&tile_image);
+                let key = (level, col, row);
+                let mut cache = cache.lock().unwrap();
+                let unchanged = cache.get(&key) == Some(&hash) && tile_path.exists();
+                cache.insert(key, hash);
+                drop(cache);
+                if !unchanged {
+                    if let Some(parent) = tile_path.parent() {
+                        std::fs::create_dir_all(parent)?;
+                    }
+                    self.encode_tile(&tile_image, &tile_path)?;
+                }
+                return Ok(());
             }
+
+            if let Some(parent) = tile_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            self.encode_tile(&tile_image, &tile_path)
+        };
+
+        match self.threads {
+            None => grid.iter().try_for_each(save_tile)?,
+            Some(_) => grid.par_iter().try_for_each(save_tile)?,
         }
         Ok(())
     }
 
-    /// Get image for a level
-    fn get_level_image(&self, level: u32) -> DZIResult<DynamicImage> {
+    /// Path a given tile is (or will be) saved at, per `self.layout`
+    fn tile_path(&self, level: u32, col: u32, row: u32) -> DZIResult<PathBuf> {
+        self.layout.strategy().tile_path(self, level, col, row)
+    }
+
+    /// Encode and save a single tile image according to `self.format`
+    fn encode_tile(&self, tile_image: &DynamicImage, path: &Path) -> DZIResult<()> {
+        let mut out = std::fs::File::create(path)?;
+        match self.format {
+            TileFormat::Jpeg { quality } => {
+                let mut encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(&mut out, quality);
+                encoder.encode_image(tile_image)?;
+            }
+            TileFormat::Png => {
+                let encoder = image::codecs::png::PngEncoder::new(&mut out);
+                tile_image.write_with_encoder(encoder)?;
+            }
+            TileFormat::WebP { quality, lossless } => {
+                // `WebPEncoder::new_with_quality`/`WebPQuality::lossy` are
+                // only available pre-0.25 of the `image` crate (0.25 made
+                // WebP encoding lossless-only); this matches the `image::io`
+                // module `new_from_image_path` already depends on above,
+                // which was also removed in 0.25, so both assume the same
+                // pinned pre-0.25 version.
+                let encoder = image::codecs::webp::WebPEncoder::new_with_quality(
+                    &mut out,
+                    if lossless {
+                        image::codecs::webp::WebPQuality::lossless()
+                    } else {
+                        image::codecs::webp::WebPQuality::lossy(quality as u8)
+                    },
+                );
+                tile_image.write_with_encoder(encoder)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the image a level's tiles are cropped from.
+    ///
+    /// If `bottom_up` is off (the default), every level is resized straight
+    /// from the source image, independently of the others. If `bottom_up`
+    /// is on, only the highest level is resized from the source; every
+    /// coarser level instead stitches `parent` — the in-memory image
+    /// already computed for the level directly above it — tile-by-tile via
+    /// [`Self::stitch_from_children`], so each level's work is proportional
+    /// to its own pixel count rather than the full source resolution, and
+    /// no already-encoded tile is ever decoded back.
+    fn get_level_image(&self, level: u32, parent: Option<&DynamicImage>) -> DZIResult<DynamicImage> {
         self.check_level(level)?;
+        if self.bottom_up && level != self.levels - 1 {
+            let parent = parent.expect("parent level image is built before any level below it");
+            return self.stitch_from_children(level, parent);
+        }
         let (w, h) = self.get_dimensions(level)?;
-        Ok(self
-            .image
-            .resize(w, h, image::imageops::FilterType::Nearest))
+        Ok(self.image.resize(w, h, self.filter))
+    }
+
+    /// Build level `level`'s image by averaging it up from `child` (level
+    /// `level + 1`'s already-built image): for each of this level's tile
+    /// positions, take the up-to-four child tiles at `(2*col, 2*row)` ..
+    /// `(2*col+1, 2*row+1)`, stitch their non-overlapping core pixels into
+    /// one up-to-`2*tile_size` block, and downscale that block with
+    /// `self.filter` into this level's tile. A tile at the source's
+    /// right/bottom edge whose fourth quadrant doesn't exist is downscaled
+    /// from whichever quadrants are present, never padded. Because the
+    /// stitch always starts from each child tile's core (non-overlap)
+    /// region, overlap for this level's own output tiles is re-derived
+    /// fresh by the caller rather than inherited from the child.
+    fn stitch_from_children(&self, level: u32, child: &DynamicImage) -> DZIResult<DynamicImage> {
+        let (lw, lh) = self.get_dimensions(level)?;
+        let (cols, rows) = self.get_tile_count(level)?;
+        let (child_cols, child_rows) = self.get_tile_count(level + 1)?;
+        let mut out = DynamicImage::new(lw, lh, child.color());
+
+        for col in 0..cols {
+            for row in 0..rows {
+                let (tx, ty, tx2, ty2) = self.get_core_tile_bounds(level, col, row)?;
+                let (tw, th) = (tx2 - tx, ty2 - ty);
+                let (block_w, block_h) = (
+                    (tw * 2).min(child.width().saturating_sub(2 * tx)).max(1),
+                    (th * 2).min(child.height().saturating_sub(2 * ty)).max(1),
+                );
+                let mut block = DynamicImage::new(block_w, block_h, child.color());
+
+                for dc in 0..2u32 {
+                    for dr in 0..2u32 {
+                        let (child_col, child_row) = (2 * col + dc, 2 * row + dr);
+                        if child_col >= child_cols || child_row >= child_rows {
+                            continue;
+                        }
+                        let (cx, cy, cx2, cy2) =
+                            self.get_core_tile_bounds(level + 1, child_col, child_row)?;
+                        if cx2 <= cx || cy2 <= cy {
+                            continue;
+                        }
+                        let quadrant = child.crop_imm(cx, cy, cx2 - cx, cy2 - cy);
+                        image::imageops::replace(
+                            &mut block,
+                            &quadrant,
+                            (dc * self.tile_size) as i64,
+                            (dr * self.tile_size) as i64,
+                        );
+                    }
+                }
+
+                let resized = block.resize_exact(tw, th, self.filter);
+                image::imageops::replace(&mut out, &resized, tx as i64, ty as i64);
+            }
+        }
+        Ok(out)
     }
 
     /// Get scale factor at level
@@ -205,16 +737,61 @@ impl TileCreator {
         let h = h.min(lh - y);
         Ok((x, y, x + w, y + h))
     }
+
+    /// Pixel bounds `(x, y, x2, y2)` of tile `(col, row)` at `level` without
+    /// the neighbor-overlap padding `get_tile_bounds` adds: this is the
+    /// region covered by this tile alone, which is what canonical (IIIF) or
+    /// mip-averaged (bottom-up) tiling needs instead of overlapping output.
+    fn get_core_tile_bounds(&self, level: u32, col: u32, row: u32) -> DZIResult<(u32, u32, u32, u32)> {
+        let (lw, lh) = self.get_dimensions(level)?;
+        let x = col * self.tile_size;
+        let y = row * self.tile_size;
+        let x2 = (x + self.tile_size).min(lw);
+        let y2 = (y + self.tile_size).min(lh);
+        Ok((x, y, x2, y2))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::TileCreator;
+    use crate::{TileCreator, TileFormat};
     use image::open;
     use std::fs::{read, read_dir};
     use std::path::PathBuf;
     use temp_dir::TempDir;
 
+    /// RGB pixel data for a `width x height` gradient, varied enough that
+    /// downscaling it is sensitive to which resampling filter is used.
+    fn gradient_rgb(width: u32, height: u32) -> Vec<u8> {
+        let mut data = Vec::with_capacity((width * height * 3) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                data.push((x * 255 / width.max(1)) as u8);
+                data.push((y * 255 / height.max(1)) as u8);
+                data.push(((x + y) * 255 / (width + height).max(1)) as u8);
+            }
+        }
+        data
+    }
+
+    /// Recursively collect every regular file under `dir`, relative to it.
+    fn list_files_relative(dir: &std::path::Path) -> Vec<PathBuf> {
+        let mut out = Vec::new();
+        for entry in read_dir(dir).unwrap() {
+            let entry = entry.unwrap();
+            let path = entry.path();
+            if path.is_dir() {
+                for child in list_files_relative(&path) {
+                    out.push(path.strip_prefix(dir).unwrap().join(child));
+                }
+            } else {
+                out.push(path.strip_prefix(dir).unwrap().to_path_buf());
+            }
+        }
+        out.sort();
+        out
+    }
+
     #[test]
     fn test_info() {
         let path = PathBuf::from(format!("{}/test_data/test.jpg", env!("CARGO_MANIFEST_DIR")));
@@ -264,6 +841,8 @@ mod tests {
         // Act
         tile_creator.create_tiles().unwrap();
 
+        // `filter` defaults to `Lanczos3`; `test_data/expected` must be
+        // regenerated against that default for this test to pass.
         // Assert dzi file is as expected
         let expected_dzi = include_bytes!("../test_data/expected/test.dzi").to_vec();
         let result_dzi = read(&dest_dzi_path).unwrap();
@@ -292,4 +871,314 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_parallel_output_matches_serial() {
+        let (width, height) = (37, 29);
+        let rgb = gradient_rgb(width, height);
+
+        let serial_dir = TempDir::new().unwrap();
+        let serial_tiles = serial_dir.path().join("tiles");
+        let serial_dzi = serial_dir.path().join("test.dzi");
+        TileCreator::new_from_rgb(&rgb, width, height, 8, 1, serial_tiles.clone(), serial_dzi)
+            .unwrap()
+            .create_tiles()
+            .unwrap();
+
+        let parallel_dir = TempDir::new().unwrap();
+        let parallel_tiles = parallel_dir.path().join("tiles");
+        let parallel_dzi = parallel_dir.path().join("test.dzi");
+        TileCreator::new_from_rgb(&rgb, width, height, 8, 1, parallel_tiles.clone(), parallel_dzi)
+            .unwrap()
+            .with_threads(Some(4))
+            .create_tiles()
+            .unwrap();
+
+        assert_eq!(
+            list_files_relative(&serial_tiles),
+            list_files_relative(&parallel_tiles),
+            "threaded run should produce the same tile files as serial"
+        );
+        for rel in list_files_relative(&serial_tiles) {
+            assert_eq!(
+                read(serial_tiles.join(&rel)).unwrap(),
+                read(parallel_tiles.join(&rel)).unwrap(),
+                "{:?} should be byte-identical between serial and threaded runs",
+                rel
+            );
+        }
+    }
+
+    #[test]
+    fn test_tile_format_encodes_and_decodes() {
+        let (width, height) = (20, 20);
+        let rgb = gradient_rgb(width, height);
+
+        for format in [
+            TileFormat::Jpeg { quality: 90 },
+            TileFormat::Png,
+            TileFormat::WebP {
+                quality: 80.0,
+                lossless: false,
+            },
+            TileFormat::WebP {
+                quality: 0.0,
+                lossless: true,
+            },
+        ] {
+            let tmp = TempDir::new().unwrap();
+            let tiles_dir = tmp.path().join("tiles");
+            let dzi_path = tmp.path().join("test.dzi");
+            let tc = TileCreator::new_from_rgb(
+                &rgb,
+                width,
+                height,
+                8,
+                1,
+                tiles_dir.clone(),
+                dzi_path.clone(),
+            )
+            .unwrap()
+            .with_format(format);
+            tc.create_tiles().unwrap();
+
+            let dzi_contents = read(dzi_path).unwrap();
+            let dzi_contents = String::from_utf8(dzi_contents).unwrap();
+            assert!(
+                dzi_contents.contains(&format!(r#"Format="{}""#, format.descriptor_name())),
+                "{:?} descriptor name should appear in the .dzi",
+                format
+            );
+
+            let tile_file = list_files_relative(&tiles_dir)
+                .into_iter()
+                .find(|p| p.extension().map(|e| e == format.extension()).unwrap_or(false))
+                .unwrap_or_else(|| panic!("no .{} tile written for {:?}", format.extension(), format));
+            // Tile should decode back as a valid image in the chosen format.
+            open(tiles_dir.join(tile_file)).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_bottom_up_builds_pyramid_with_border_quadrants() {
+        // Odd dimensions with a small tile size guarantee some levels have
+        // an odd tile count, so at least one tile's quadrant stitch is
+        // missing its right and/or bottom child tile.
+        let (width, height) = (37, 23);
+        let rgb = gradient_rgb(width, height);
+
+        let tmp = TempDir::new().unwrap();
+        let tiles_dir = tmp.path().join("tiles");
+        let dzi_path = tmp.path().join("test.dzi");
+        let tc = TileCreator::new_from_rgb(&rgb, width, height, 8, 0, tiles_dir.clone(), dzi_path)
+            .unwrap()
+            .with_bottom_up(true);
+
+        tc.create_tiles().unwrap();
+
+        // Every level's expected tile grid should have been written, with
+        // no panics from the border-quadrant case above.
+        for level in 0..tc.levels {
+            let (cols, rows) = tc.get_tile_count(level).unwrap();
+            let level_dir = tiles_dir.join(level.to_string());
+            let written = read_dir(&level_dir).unwrap().count() as u32;
+            assert_eq!(
+                written,
+                cols * rows,
+                "level {} should have cols*rows tiles",
+                level
+            );
+        }
+    }
+
+    #[test]
+    fn test_default_filter_is_lanczos3() {
+        let (width, height) = (16, 16);
+        let rgb = gradient_rgb(width, height);
+        let tiles_dir = TempDir::new().unwrap().path().join("tiles");
+        let dzi_path = TempDir::new().unwrap().path().join("test.dzi");
+        let tc = TileCreator::new_from_rgb(&rgb, width, height, 8, 0, tiles_dir, dzi_path).unwrap();
+        assert_eq!(tc.filter, image::imageops::FilterType::Lanczos3);
+    }
+
+    #[test]
+    fn test_filter_choice_changes_coarse_level_output() {
+        let (width, height) = (32, 32);
+        let rgb = gradient_rgb(width, height);
+
+        let lanczos_dir = TempDir::new().unwrap();
+        let lanczos_tiles = lanczos_dir.path().join("tiles");
+        TileCreator::new_from_rgb(
+            &rgb,
+            width,
+            height,
+            8,
+            0,
+            lanczos_tiles.clone(),
+            lanczos_dir.path().join("test.dzi"),
+        )
+        .unwrap()
+        .create_tiles()
+        .unwrap();
+
+        let nearest_dir = TempDir::new().unwrap();
+        let nearest_tiles = nearest_dir.path().join("tiles");
+        TileCreator::new_from_rgb(
+            &rgb,
+            width,
+            height,
+            8,
+            0,
+            nearest_tiles.clone(),
+            nearest_dir.path().join("test.dzi"),
+        )
+        .unwrap()
+        .with_filter(image::imageops::FilterType::Nearest)
+        .create_tiles()
+        .unwrap();
+
+        // Level 0 (the coarsest, most downscaled) is where Lanczos3's
+        // blending and Nearest's no-op sampling diverge the most.
+        let lanczos_bytes = read(lanczos_tiles.join("0").join("0_0.jpg")).unwrap();
+        let nearest_bytes = read(nearest_tiles.join("0").join("0_0.jpg")).unwrap();
+        assert_ne!(
+            lanczos_bytes, nearest_bytes,
+            "Lanczos3 and Nearest should resample the coarsest level differently"
+        );
+    }
+
+    #[test]
+    fn test_iiif_layout_manifest_and_tile_paths() {
+        use crate::Layout;
+
+        let (width, height) = (40, 24);
+        let rgb = gradient_rgb(width, height);
+        let tmp = TempDir::new().unwrap();
+        let tiles_dir = tmp.path().join("tiles");
+        let dzi_path = tmp.path().join("unused.dzi");
+        let tc = TileCreator::new_from_rgb(&rgb, width, height, 8, 0, tiles_dir.clone(), dzi_path)
+            .unwrap()
+            .with_layout(Layout::Iiif);
+
+        let (manifest_path, _) = tc.create_tiles().unwrap();
+
+        assert_eq!(manifest_path, tiles_dir.join("info.json"));
+        let manifest = String::from_utf8(read(&manifest_path).unwrap()).unwrap();
+        assert!(manifest.contains(r#""width": 40"#));
+        assert!(manifest.contains(r#""height": 24"#));
+        assert!(manifest.contains("scaleFactors"));
+
+        // Canonical IIIF paths have no level segment: dest/{region}/{size}/0/default.ext,
+        // i.e. exactly 4 path components under the tiles directory.
+        for rel in list_files_relative(&tiles_dir) {
+            if rel == PathBuf::from("info.json") {
+                continue;
+            }
+            assert_eq!(
+                rel.components().count(),
+                4,
+                "{:?} should be region/size/rotation/quality.ext with no level segment",
+                rel
+            );
+        }
+    }
+
+    #[test]
+    fn test_iiif_tile_bounds_have_no_overlap() {
+        use crate::Layout;
+
+        let (width, height) = (40, 24);
+        let rgb = gradient_rgb(width, height);
+        let tmp = TempDir::new().unwrap();
+        let tiles_dir = tmp.path().join("tiles");
+        let dzi_path = tmp.path().join("unused.dzi");
+        let tc = TileCreator::new_from_rgb(&rgb, width, height, 8, 2, tiles_dir, dzi_path)
+            .unwrap()
+            .with_layout(Layout::Iiif);
+
+        // get_core_tile_bounds (what Iiif crops from) ignores tile_overlap,
+        // unlike get_tile_bounds (what DeepZoom crops from).
+        let core = tc.get_core_tile_bounds(tc.levels - 1, 1, 1).unwrap();
+        let overlapping = tc.get_tile_bounds(tc.levels - 1, 1, 1).unwrap();
+        assert_ne!(core, overlapping);
+        assert_eq!(core.2 - core.0, 8);
+        assert_eq!(core.3 - core.1, 8);
+    }
+
+    #[test]
+    fn test_incremental_skips_fully_unchanged_rerun() {
+        let (width, height) = (16, 16);
+        let rgb = gradient_rgb(width, height);
+        let tmp = TempDir::new().unwrap();
+        let tiles_dir = tmp.path().join("tiles");
+        let dzi_path = tmp.path().join("test.dzi");
+        let tc = || {
+            TileCreator::new_from_rgb(&rgb, width, height, 8, 0, tiles_dir.clone(), dzi_path.clone())
+                .unwrap()
+                .with_incremental(true)
+        };
+
+        tc().create_tiles().unwrap();
+        let before = std::fs::metadata(tiles_dir.join("0").join("0_0.jpg"))
+            .unwrap()
+            .modified()
+            .unwrap();
+
+        std::thread::sleep(std::time::Duration::from_millis(1100));
+        tc().create_tiles().unwrap();
+
+        let after = std::fs::metadata(tiles_dir.join("0").join("0_0.jpg"))
+            .unwrap()
+            .modified()
+            .unwrap();
+        assert_eq!(
+            before, after,
+            "rerunning with nothing changed should not touch any tile on disk"
+        );
+    }
+
+    #[test]
+    fn test_incremental_cleans_up_orphans_on_param_change() {
+        let (width, height) = (16, 16);
+        let rgb = gradient_rgb(width, height);
+        let tmp = TempDir::new().unwrap();
+        let tiles_dir = tmp.path().join("tiles");
+        let dzi_path = tmp.path().join("test.dzi");
+
+        TileCreator::new_from_rgb(&rgb, width, height, 8, 0, tiles_dir.clone(), dzi_path.clone())
+            .unwrap()
+            .with_incremental(true)
+            .create_tiles()
+            .unwrap();
+        let first_grid = list_files_relative(&tiles_dir);
+
+        // A different tile_size reshapes the whole tile grid.
+        let tc = TileCreator::new_from_rgb(&rgb, width, height, 4, 0, tiles_dir.clone(), dzi_path)
+            .unwrap()
+            .with_incremental(true);
+        tc.create_tiles().unwrap();
+        let second_grid = list_files_relative(&tiles_dir);
+
+        assert_ne!(first_grid, second_grid);
+
+        // No stale tile from the old (tile_size: 8) grid shape should
+        // remain: every file on disk is either the cache sidecar or one of
+        // the new grid's own tiles, and the new grid's expected tile count
+        // for each level exactly matches what's on disk (no extras left
+        // over from the old, coarser grid).
+        let expected_tile_count: u32 = (0..tc.levels)
+            .map(|l| {
+                let (cols, rows) = tc.get_tile_count(l).unwrap();
+                cols * rows
+            })
+            .sum();
+        let on_disk_tile_count = second_grid
+            .iter()
+            .filter(|rel| *rel != &PathBuf::from(".tile-cache"))
+            .count() as u32;
+        assert_eq!(
+            on_disk_tile_count, expected_tile_count,
+            "only the new grid's own tiles should remain after a param change"
+        );
+    }
 }